@@ -1,29 +1,1197 @@
-use std::fs::OpenOptions;
-use std::io::Write;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
+const LOG_PATH: &str = "OPERATOR_LOG.jsonl";
+const INDEX_PATH: &str = "OPERATOR_LOG.index.jsonl";
+
+/// Chunk size used when scanning backward from the end of the log file.
+const TAIL_CHUNK_SIZE: u64 = 8 * 1024;
+
+/// Rotate once the active log reaches this size. Tunable at runtime via
+/// `set_operator_log_max_segment_bytes`.
+static MAX_SEGMENT_BYTES: AtomicU64 = AtomicU64::new(64 * 1024 * 1024);
+
+/// Number of rotated segments to keep on disk before the oldest are deleted.
+const MAX_RETAINED_SEGMENTS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// A single, structurally-valid entry in `OPERATOR_LOG.jsonl`. `timestamp`
+/// is always server-assigned; whatever an incoming request sends for it is
+/// discarded by `append_operator_log`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperatorLogEntry {
+    #[serde(default)]
+    pub timestamp: i64,
+    pub level: LogLevel,
+    pub actor: String,
+    pub action: String,
+    #[serde(default)]
+    pub payload: serde_json::Value,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct OperatorLogQuery {
+    #[serde(default)]
+    pub since_ts: Option<i64>,
+    #[serde(default)]
+    pub until_ts: Option<i64>,
+    #[serde(default)]
+    pub level: Option<LogLevel>,
+    #[serde(default)]
+    pub actor: Option<String>,
+}
+
+impl OperatorLogQuery {
+    fn matches(&self, entry: &OperatorLogEntry) -> bool {
+        if let Some(since_ts) = self.since_ts {
+            if entry.timestamp < since_ts {
+                return false;
+            }
+        }
+        if let Some(until_ts) = self.until_ts {
+            if entry.timestamp > until_ts {
+                return false;
+            }
+        }
+        if let Some(level) = self.level {
+            if entry.level != level {
+                return false;
+            }
+        }
+        if let Some(actor) = &self.actor {
+            if &entry.actor != actor {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Parses and validates `entry_json` into an `OperatorLogEntry`, stamping it
+/// with the server's own clock before it's queued for the active log.
 #[tauri::command]
 pub fn append_operator_log(entry_json: String) -> Result<(), String> {
-    let mut file = OpenOptions::new()
+    let mut entry: OperatorLogEntry =
+        serde_json::from_str(&entry_json).map_err(|e| describe_parse_error(&e))?;
+    entry.timestamp = unix_timestamp_millis();
+
+    let line = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+    enqueue_log_line(line)
+}
+
+/// One record from `OPERATOR_LOG.index.jsonl`, as written by
+/// `rotate_operator_log`.
+#[derive(Debug, Deserialize)]
+struct SegmentIndexRecord {
+    segment: String,
+    first_ts: i64,
+    last_ts: i64,
+}
+
+/// Returns entries matching `filter`, drawn from the active log and, when
+/// `filter` reaches further back than the active log's own start, from
+/// whichever rotated segments overlap the requested range. This is the
+/// `future queries can locate the right segment` use of the index promised
+/// when rotation was introduced. Entries that fail to parse as
+/// `OperatorLogEntry` (e.g. from before this schema existed) are skipped
+/// rather than failing the whole query.
+#[tauri::command]
+pub fn query_operator_log(filter: OperatorLogQuery) -> Result<Vec<OperatorLogEntry>, String> {
+    let mut matched = Vec::new();
+
+    for record in read_segment_index()? {
+        if segment_overlaps_query(&record, &filter) {
+            read_matching_entries(&record.segment, &filter, &mut matched)?;
+        }
+    }
+
+    read_matching_entries(LOG_PATH, &filter, &mut matched)?;
+
+    matched.sort_by_key(|entry| entry.timestamp);
+    Ok(matched)
+}
+
+fn read_segment_index() -> Result<Vec<SegmentIndexRecord>, String> {
+    let contents = match std::fs::read_to_string(INDEX_PATH) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    Ok(contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn segment_overlaps_query(record: &SegmentIndexRecord, filter: &OperatorLogQuery) -> bool {
+    if let Some(until_ts) = filter.until_ts {
+        if record.first_ts > until_ts {
+            return false;
+        }
+    }
+    if let Some(since_ts) = filter.since_ts {
+        if record.last_ts < since_ts {
+            return false;
+        }
+    }
+    true
+}
+
+fn read_matching_entries(
+    path: &str,
+    filter: &OperatorLogQuery,
+    out: &mut Vec<OperatorLogEntry>,
+) -> Result<(), String> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    for line in contents.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(entry) = serde_json::from_str::<OperatorLogEntry>(line) else {
+            continue;
+        };
+        if filter.matches(&entry) {
+            out.push(entry);
+        }
+    }
+
+    Ok(())
+}
+
+/// Sets the size threshold that triggers rotation, in bytes. Takes effect
+/// on the next size check the writer makes; a check that has already read
+/// the old threshold still uses it.
+#[tauri::command]
+pub fn set_operator_log_max_segment_bytes(bytes: u64) -> Result<(), String> {
+    if bytes == 0 {
+        return Err("max_segment_bytes must be greater than zero".to_string());
+    }
+    MAX_SEGMENT_BYTES.store(bytes, Ordering::Relaxed);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_operator_log_segments() -> Result<Vec<String>, String> {
+    match std::fs::read_to_string(INDEX_PATH) {
+        Ok(contents) => Ok(contents.lines().map(|s| s.to_string()).collect()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Formats a `serde_json` parse failure with the line/column of the
+/// offending token so the frontend can point the operator at it directly.
+fn describe_parse_error(err: &serde_json::Error) -> String {
+    format!(
+        "invalid operator log entry at line {}, column {}: {}",
+        err.line(),
+        err.column(),
+        err
+    )
+}
+
+#[cfg(not(feature = "tokio-log-writer"))]
+fn enqueue_log_line(line: String) -> Result<(), String> {
+    writer::enqueue_line(line)
+}
+
+#[cfg(feature = "tokio-log-writer")]
+fn enqueue_log_line(line: String) -> Result<(), String> {
+    tokio_writer::enqueue_line(line)
+}
+
+/// Rotates the active log if it has crossed `MAX_SEGMENT_BYTES`. Returns
+/// whether a rotation happened, so a caller holding an open file handle
+/// knows to reopen it.
+fn rotate_if_oversized() -> Result<bool, String> {
+    let size = match std::fs::metadata(LOG_PATH) {
+        Ok(meta) => meta.len(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    if size >= MAX_SEGMENT_BYTES.load(Ordering::Relaxed) {
+        rotate_operator_log()?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Renames the active log to a timestamped segment, appends an index record
+/// describing it, and prunes segments beyond `MAX_RETAINED_SEGMENTS`. Outside
+/// tests, only called from the background writer (thread or tokio task),
+/// which is the sole mutator of the active log file.
+fn rotate_operator_log() -> Result<(), String> {
+    let metadata = match std::fs::metadata(LOG_PATH) {
+        Ok(meta) => meta,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.to_string()),
+    };
+    if metadata.len() == 0 {
+        return Ok(());
+    }
+
+    let contents = std::fs::read_to_string(LOG_PATH).map_err(|e| e.to_string())?;
+    let line_count = contents.lines().count();
+    let first_ts = contents.lines().next().and_then(extract_timestamp_field);
+    let last_ts = contents.lines().last().and_then(extract_timestamp_field);
+
+    let rotated_at = unix_timestamp();
+    let segment = unique_segment_path();
+    std::fs::rename(LOG_PATH, &segment).map_err(|e| e.to_string())?;
+
+    let index_record = format!(
+        "{{\"segment\":\"{}\",\"first_ts\":{},\"last_ts\":{},\"lines\":{},\"bytes\":{}}}",
+        segment,
+        first_ts.unwrap_or(rotated_at as i64),
+        last_ts.unwrap_or(rotated_at as i64),
+        line_count,
+        metadata.len(),
+    );
+    let mut index_file = OpenOptions::new()
         .create(true)
         .append(true)
-        .open("OPERATOR_LOG.jsonl")
+        .open(INDEX_PATH)
         .map_err(|e| e.to_string())?;
-
-    file.write_all(entry_json.as_bytes())
-        .and_then(|_| file.write_all(b"\n"))
+    index_file
+        .write_all(index_record.as_bytes())
+        .and_then(|_| index_file.write_all(b"\n"))
         .map_err(|e| e.to_string())?;
 
+    prune_old_segments()
+}
+
+fn prune_old_segments() -> Result<(), String> {
+    let mut segments = Vec::new();
+    for entry in std::fs::read_dir(".").map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+        if name.starts_with("OPERATOR_LOG.") && name.ends_with(".jsonl") && name != INDEX_PATH {
+            segments.push(name.to_string());
+        }
+    }
+
+    // Segment names embed a unix timestamp, so lexical order is chronological.
+    segments.sort();
+    while segments.len() > MAX_RETAINED_SEGMENTS {
+        let oldest = segments.remove(0);
+        std::fs::remove_file(&oldest).map_err(|e| e.to_string())?;
+    }
+
     Ok(())
 }
 
+/// Best-effort scan for a `"timestamp":<number>` field without pulling in a
+/// JSON parser just for this. Returns `None` for entries that don't have one.
+fn extract_timestamp_field(line: &str) -> Option<i64> {
+    const KEY: &str = "\"timestamp\":";
+    let start = line.find(KEY)? + KEY.len();
+    let rest = line[start..].trim_start();
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '-'))
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Picks a segment filename for the active log being rotated right now.
+/// Uses nanosecond resolution to make same-second collisions unlikely, and
+/// always appends a zero-padded counter so a genuine collision (two
+/// rotations landing on the same instant, or a clock that doesn't have
+/// nanosecond resolution) disambiguates instead of silently overwriting an
+/// existing segment on rename. The counter is zero-padded and always
+/// present (rather than only appended on collision) so every segment name
+/// has the same shape and lexical order still matches creation order —
+/// an unpadded or sometimes-absent suffix would sort `-1` before the bare
+/// name it's meant to follow.
+fn unique_segment_path() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+
+    let mut suffix = 0u32;
+    loop {
+        let candidate = format!("OPERATOR_LOG.{}.{:04}.jsonl", nanos, suffix);
+        if !std::path::Path::new(&candidate).exists() {
+            return candidate;
+        }
+        suffix += 1;
+    }
+}
+
+fn unix_timestamp_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// Owns the append-mode file handle on a dedicated thread so
+/// `append_operator_log` never blocks the calling command handler on disk
+/// I/O. Active unless the app is already running on a tokio executor and
+/// opts into `tokio-log-writer` instead.
+#[cfg(not(feature = "tokio-log-writer"))]
+mod writer {
+    use super::{rotate_if_oversized, rotate_operator_log, File, OpenOptions, Write, LOG_PATH};
+    use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, SyncSender};
+    use std::sync::OnceLock;
+    use std::time::Duration;
+
+    enum Command {
+        Append(String),
+        Flush(SyncSender<Result<(), String>>),
+        Rotate(SyncSender<Result<(), String>>),
+        Shutdown(SyncSender<Result<(), String>>),
+    }
+
+    fn sender() -> &'static Sender<Command> {
+        static SENDER: OnceLock<Sender<Command>> = OnceLock::new();
+        SENDER.get_or_init(|| {
+            let (tx, rx) = mpsc::channel();
+            std::thread::spawn(move || run(rx));
+            tx
+        })
+    }
+
+    pub fn enqueue_line(line: String) -> Result<(), String> {
+        sender()
+            .send(Command::Append(line))
+            .map_err(|_| "operator log writer has shut down".to_string())
+    }
+
+    /// Drains any buffered entries and fsyncs the active log so nothing
+    /// written so far is lost if the process is killed right after this
+    /// returns.
+    #[tauri::command]
+    pub fn flush_operator_log() -> Result<(), String> {
+        let (reply_tx, reply_rx) = mpsc::sync_channel(1);
+        sender()
+            .send(Command::Flush(reply_tx))
+            .map_err(|_| "operator log writer has shut down".to_string())?;
+        reply_rx
+            .recv()
+            .map_err(|_| "operator log writer dropped before replying".to_string())?
+    }
+
+    /// Drains and fsyncs like `flush_operator_log`, then terminates the
+    /// writer thread. Call this (and await it) before the app exits so a
+    /// shutdown can't race the last batch of writes.
+    #[tauri::command]
+    pub fn shutdown_operator_log() -> Result<(), String> {
+        let (reply_tx, reply_rx) = mpsc::sync_channel(1);
+        sender()
+            .send(Command::Shutdown(reply_tx))
+            .map_err(|_| "operator log writer has shut down".to_string())?;
+        reply_rx
+            .recv()
+            .map_err(|_| "operator log writer dropped before replying".to_string())?
+    }
+
+    #[tauri::command]
+    pub fn rotate_operator_log_now() -> Result<(), String> {
+        let (reply_tx, reply_rx) = mpsc::sync_channel(1);
+        sender()
+            .send(Command::Rotate(reply_tx))
+            .map_err(|_| "operator log writer has shut down".to_string())?;
+        reply_rx
+            .recv()
+            .map_err(|_| "operator log writer dropped before replying".to_string())?
+    }
+
+    fn open_log_file() -> Option<File> {
+        OpenOptions::new().create(true).append(true).open(LOG_PATH).ok()
+    }
+
+    fn run(rx: Receiver<Command>) {
+        let mut file = open_log_file();
+        let mut shutting_down = false;
+
+        loop {
+            let first = match rx.recv_timeout(Duration::from_millis(250)) {
+                Ok(cmd) => cmd,
+                Err(RecvTimeoutError::Timeout) => {
+                    if let Some(f) = file.as_mut() {
+                        let _ = f.flush();
+                    }
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+
+            // Drain whatever else is already queued so a burst of appends
+            // costs one write_all instead of one per entry.
+            let mut batch = vec![first];
+            while let Ok(cmd) = rx.try_recv() {
+                batch.push(cmd);
+            }
+
+            let mut pending: Vec<u8> = Vec::new();
+            for cmd in batch {
+                match cmd {
+                    Command::Append(entry) => {
+                        pending.extend_from_slice(entry.as_bytes());
+                        pending.push(b'\n');
+                    }
+                    Command::Flush(reply) => {
+                        let result = write_pending(&mut file, &mut pending)
+                            .and_then(|_| sync_file(&mut file));
+                        let _ = reply.send(result);
+                    }
+                    Command::Rotate(reply) => {
+                        let result = write_pending(&mut file, &mut pending).and_then(|_| {
+                            rotate_operator_log()?;
+                            file = open_log_file();
+                            Ok(())
+                        });
+                        let _ = reply.send(result);
+                    }
+                    Command::Shutdown(reply) => {
+                        let result = write_pending(&mut file, &mut pending)
+                            .and_then(|_| sync_file(&mut file));
+                        let _ = reply.send(result);
+                        shutting_down = true;
+                    }
+                }
+            }
+
+            let _ = write_pending(&mut file, &mut pending);
+
+            if shutting_down {
+                break;
+            }
+        }
+
+        if let Some(f) = file.as_mut() {
+            let _ = f.sync_all();
+        }
+    }
+
+    /// Writes and flushes whatever is buffered, then rotates if the file has
+    /// crossed the size threshold. Leaves `pending` empty either way.
+    fn write_pending(file: &mut Option<File>, pending: &mut Vec<u8>) -> Result<(), String> {
+        if !pending.is_empty() {
+            if file.is_none() {
+                *file = open_log_file();
+            }
+            if let Some(f) = file.as_mut() {
+                f.write_all(pending).map_err(|e| e.to_string())?;
+            }
+            pending.clear();
+        }
+
+        if let Some(f) = file.as_mut() {
+            f.flush().map_err(|e| e.to_string())?;
+        }
+
+        if rotate_if_oversized()? {
+            *file = open_log_file();
+        }
+
+        Ok(())
+    }
+
+    /// Forces buffered writes out to the underlying storage device. Only
+    /// called for explicit flush/shutdown requests, never on the routine
+    /// per-batch path, since fsync is too expensive to pay on every tick.
+    fn sync_file(file: &mut Option<File>) -> Result<(), String> {
+        if let Some(f) = file.as_mut() {
+            f.sync_all().map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// Tokio-backed equivalent of `writer`, for apps that already run on an
+/// async executor and would rather not spin up an extra native thread.
+#[cfg(feature = "tokio-log-writer")]
+mod tokio_writer {
+    use super::{rotate_if_oversized, rotate_operator_log, LOG_PATH};
+    use std::sync::OnceLock;
+    use tokio::fs::{File, OpenOptions};
+    use tokio::io::AsyncWriteExt;
+    use tokio::sync::{mpsc, oneshot};
+
+    enum Command {
+        Append(String),
+        Flush(oneshot::Sender<Result<(), String>>),
+        Rotate(oneshot::Sender<Result<(), String>>),
+        Shutdown(oneshot::Sender<Result<(), String>>),
+    }
+
+    fn sender() -> &'static mpsc::UnboundedSender<Command> {
+        static SENDER: OnceLock<mpsc::UnboundedSender<Command>> = OnceLock::new();
+        SENDER.get_or_init(|| {
+            let (tx, rx) = mpsc::unbounded_channel();
+            tokio::spawn(run(rx));
+            tx
+        })
+    }
+
+    pub fn enqueue_line(line: String) -> Result<(), String> {
+        sender()
+            .send(Command::Append(line))
+            .map_err(|_| "operator log writer has shut down".to_string())
+    }
+
+    /// Drains any buffered entries and fsyncs the active log so nothing
+    /// written so far is lost if the process is killed right after this
+    /// returns.
+    #[tauri::command]
+    pub async fn flush_operator_log() -> Result<(), String> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        sender()
+            .send(Command::Flush(reply_tx))
+            .map_err(|_| "operator log writer has shut down".to_string())?;
+        reply_rx
+            .await
+            .map_err(|_| "operator log writer dropped before replying".to_string())?
+    }
+
+    /// Drains and fsyncs like `flush_operator_log`, then terminates the
+    /// writer task. Call this (and await it) before the app exits so a
+    /// shutdown can't race the last batch of writes.
+    #[tauri::command]
+    pub async fn shutdown_operator_log() -> Result<(), String> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        sender()
+            .send(Command::Shutdown(reply_tx))
+            .map_err(|_| "operator log writer has shut down".to_string())?;
+        reply_rx
+            .await
+            .map_err(|_| "operator log writer dropped before replying".to_string())?
+    }
+
+    #[tauri::command]
+    pub async fn rotate_operator_log_now() -> Result<(), String> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        sender()
+            .send(Command::Rotate(reply_tx))
+            .map_err(|_| "operator log writer has shut down".to_string())?;
+        reply_rx
+            .await
+            .map_err(|_| "operator log writer dropped before replying".to_string())?
+    }
+
+    async fn open_log_file() -> Option<File> {
+        OpenOptions::new().create(true).append(true).open(LOG_PATH).await.ok()
+    }
+
+    async fn run(mut rx: mpsc::UnboundedReceiver<Command>) {
+        let mut file = open_log_file().await;
+        let mut shutting_down = false;
+
+        while let Some(first) = rx.recv().await {
+            let mut batch = vec![first];
+            while let Ok(cmd) = rx.try_recv() {
+                batch.push(cmd);
+            }
+
+            let mut pending: Vec<u8> = Vec::new();
+            for cmd in batch {
+                match cmd {
+                    Command::Append(entry) => {
+                        pending.extend_from_slice(entry.as_bytes());
+                        pending.push(b'\n');
+                    }
+                    Command::Flush(reply) => {
+                        let result = match write_pending(&mut file, &mut pending).await {
+                            Ok(()) => sync_file(&mut file).await,
+                            Err(e) => Err(e),
+                        };
+                        let _ = reply.send(result);
+                    }
+                    Command::Rotate(reply) => {
+                        let result = match write_pending(&mut file, &mut pending).await {
+                            // Rotation runs with blocking std calls on this
+                            // task: it reads the whole active log once to
+                            // build the index record, then renames it and
+                            // prunes old segments.
+                            Ok(()) => rotate_operator_log().map(|_| ()),
+                            Err(e) => Err(e),
+                        };
+                        if result.is_ok() {
+                            file = open_log_file().await;
+                        }
+                        let _ = reply.send(result);
+                    }
+                    Command::Shutdown(reply) => {
+                        let result = match write_pending(&mut file, &mut pending).await {
+                            Ok(()) => sync_file(&mut file).await,
+                            Err(e) => Err(e),
+                        };
+                        let _ = reply.send(result);
+                        shutting_down = true;
+                    }
+                }
+            }
+
+            let _ = write_pending(&mut file, &mut pending).await;
+
+            if shutting_down {
+                break;
+            }
+        }
+
+        if let Some(f) = file.as_mut() {
+            let _ = f.sync_all().await;
+        }
+    }
+
+    async fn write_pending(file: &mut Option<File>, pending: &mut Vec<u8>) -> Result<(), String> {
+        if !pending.is_empty() {
+            if file.is_none() {
+                *file = open_log_file().await;
+            }
+            if let Some(f) = file.as_mut() {
+                f.write_all(pending).await.map_err(|e| e.to_string())?;
+            }
+            pending.clear();
+        }
+
+        if let Some(f) = file.as_mut() {
+            f.flush().await.map_err(|e| e.to_string())?;
+        }
+
+        if rotate_if_oversized()? {
+            *file = open_log_file().await;
+        }
+
+        Ok(())
+    }
+
+    /// Forces buffered writes out to the underlying storage device. Only
+    /// called for explicit flush/shutdown requests, never on the routine
+    /// per-batch path, since fsync is too expensive to pay on every tick.
+    async fn sync_file(file: &mut Option<File>) -> Result<(), String> {
+        if let Some(f) = file.as_mut() {
+            f.sync_all().await.map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "tokio-log-writer"))]
+pub use writer::{flush_operator_log, rotate_operator_log_now, shutdown_operator_log};
+
+#[cfg(feature = "tokio-log-writer")]
+pub use tokio_writer::{flush_operator_log, rotate_operator_log_now, shutdown_operator_log};
+
 #[tauri::command]
 pub fn read_operator_log_tail(lines: usize) -> Result<Vec<String>, String> {
-    let content = std::fs::read_to_string("OPERATOR_LOG.jsonl").map_err(|e| e.to_string())?;
+    let mut file = File::open(LOG_PATH).map_err(|e| e.to_string())?;
+    tail_lines(&mut file, lines)
+}
 
-    Ok(content
-        .lines()
-        .rev()
-        .take(lines)
-        .map(|s| s.to_string())
-        .collect())
+/// Core of `read_operator_log_tail`, kept separate from the file path so it
+/// can be exercised directly against an arbitrary open file in tests.
+/// Returns up to `lines` entries, newest first, matching the ordering of
+/// the full-file-read implementation this replaced.
+fn tail_lines(file: &mut File, lines: usize) -> Result<Vec<String>, String> {
+    let file_len = file.metadata().map_err(|e| e.to_string())?.len();
+
+    // Read backward in fixed-size chunks, prepending each to `tail`, until
+    // we've seen enough newlines to cover the requested number of lines or
+    // we've walked all the way back to the start of the file.
+    let mut tail: Vec<u8> = Vec::new();
+    let mut newline_count = 0usize;
+    let mut pos = file_len;
+
+    while pos > 0 && newline_count <= lines {
+        let chunk_len = TAIL_CHUNK_SIZE.min(pos);
+        pos -= chunk_len;
+
+        file.seek(SeekFrom::Start(pos)).map_err(|e| e.to_string())?;
+        let mut chunk = vec![0u8; chunk_len as usize];
+        file.read_exact(&mut chunk).map_err(|e| e.to_string())?;
+
+        newline_count += chunk.iter().filter(|&&b| b == b'\n').count();
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+    }
+
+    // Decode once the full tail window has been assembled, so a chunk
+    // boundary landing inside a multibyte UTF-8 sequence never corrupts it.
+    let text = String::from_utf8_lossy(&tail);
+    let mut collected: Vec<&str> = text.split('\n').collect();
+
+    // The first entry is only a partial line if the window actually starts
+    // mid-line. That's true whenever pos > 0, *except* when the chunk
+    // boundary happens to land exactly on a '\n' — in that case the byte
+    // right before `pos` closed out the previous line, so collected[0] is
+    // already a complete line and is kept. Either way it is never among the
+    // returned entries when pos > 0: the scan only stops early after seeing
+    // more than `lines` newlines, so at least `lines` entries follow it.
+    if pos > 0 {
+        let mut prev_byte = [0u8; 1];
+        file.seek(SeekFrom::Start(pos - 1)).map_err(|e| e.to_string())?;
+        file.read_exact(&mut prev_byte).map_err(|e| e.to_string())?;
+        if prev_byte[0] != b'\n' {
+            collected.remove(0);
+        }
+    }
+
+    // A file ending in a newline yields one trailing empty split segment;
+    // drop it so it isn't mistaken for a blank last line.
+    if collected.last().is_some_and(|s| s.is_empty()) {
+        collected.pop();
+    }
+
+    let start = collected.len().saturating_sub(lines);
+    let mut result: Vec<String> = collected[start..].iter().map(|s| s.to_string()).collect();
+    result.reverse();
+    Ok(result)
+}
+
+/// Live tailing of `OPERATOR_LOG.jsonl` to the frontend, gated behind the
+/// `log-watch` feature so builds that don't need a live console can skip the
+/// `notify` dependency entirely.
+#[cfg(feature = "log-watch")]
+mod stream {
+    use super::{File, Read, Seek, SeekFrom, LOG_PATH};
+    use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+    use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use tauri::Window;
+
+    struct StreamHandle {
+        stop_tx: Sender<()>,
+    }
+
+    static STREAM: Mutex<Option<StreamHandle>> = Mutex::new(None);
+
+    #[tauri::command]
+    pub fn start_operator_log_stream(window: Window) -> Result<(), String> {
+        let mut guard = STREAM
+            .lock()
+            .map_err(|_| "operator log stream lock poisoned".to_string())?;
+        if guard.is_some() {
+            return Ok(());
+        }
+
+        let (stop_tx, stop_rx) = channel::<()>();
+        let (event_tx, event_rx) = channel();
+
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
+            let _ = event_tx.send(res);
+        })
+        .map_err(|e| e.to_string())?;
+        // Watch the containing directory rather than the bare file path.
+        // Rotation renames the active file to a segment and then creates a
+        // fresh one at the same path; a watch on the path itself stays
+        // bound to the renamed-away inode and never fires on the new file,
+        // so the stream would silently go dead after the first rotation.
+        watcher
+            .watch(std::path::Path::new("."), RecursiveMode::NonRecursive)
+            .map_err(|e| e.to_string())?;
+
+        let mut offset = std::fs::metadata(LOG_PATH).map(|m| m.len()).unwrap_or(0);
+
+        std::thread::spawn(move || {
+            let _watcher = watcher; // keep the watcher alive for the thread's lifetime
+            loop {
+                if stop_rx.try_recv().is_ok() {
+                    break;
+                }
+                match event_rx.recv_timeout(Duration::from_millis(200)) {
+                    Ok(Ok(event)) => {
+                        let affects_log = event
+                            .paths
+                            .iter()
+                            .any(|p| p.file_name().and_then(|n| n.to_str()) == Some(LOG_PATH));
+                        if affects_log
+                            && matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_))
+                        {
+                            offset = emit_new_lines(&window, offset);
+                        }
+                    }
+                    Ok(Err(_)) => {}
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+
+        *guard = Some(StreamHandle { stop_tx });
+        Ok(())
+    }
+
+    #[tauri::command]
+    pub fn stop_operator_log_stream() -> Result<(), String> {
+        let mut guard = STREAM
+            .lock()
+            .map_err(|_| "operator log stream lock poisoned".to_string())?;
+        if let Some(handle) = guard.take() {
+            let _ = handle.stop_tx.send(());
+        }
+        Ok(())
+    }
+
+    /// Reads from `offset` to EOF, emits each complete line, and returns the
+    /// new offset. Resets to 0 first if the file is now shorter than
+    /// `offset`, which happens when rotation or truncation lands underneath us.
+    fn emit_new_lines(window: &Window, offset: u64) -> u64 {
+        let Ok(mut file) = File::open(LOG_PATH) else {
+            return offset;
+        };
+        let (next_offset, lines) = read_new_lines(&mut file, offset);
+        for line in lines {
+            let _ = window.emit("operator-log-line", line);
+        }
+        next_offset
+    }
+
+    /// Pure core of `emit_new_lines`: reads whatever complete lines have been
+    /// appended since `offset`, returning them along with the offset the next
+    /// read should resume from. Split out from `emit_new_lines` so the
+    /// truncation-detection and partial-line handling can be tested without a
+    /// `Window` to emit into.
+    fn read_new_lines(file: &mut File, offset: u64) -> (u64, Vec<String>) {
+        let len = file.metadata().map(|m| m.len()).unwrap_or(offset);
+        let read_offset = if len < offset { 0 } else { offset };
+
+        if file.seek(SeekFrom::Start(read_offset)).is_err() {
+            return (offset, Vec::new());
+        }
+
+        let mut buf = Vec::new();
+        if file.read_to_end(&mut buf).is_err() {
+            return (offset, Vec::new());
+        }
+
+        let text = String::from_utf8_lossy(&buf);
+        let mut consumed = 0usize;
+        let mut lines = Vec::new();
+        for line in text.split_inclusive('\n') {
+            if !line.ends_with('\n') {
+                break; // partial line; wait for the next change notification
+            }
+            consumed += line.len();
+            let trimmed = line.trim_end_matches('\n');
+            if !trimmed.is_empty() {
+                lines.push(trimmed.to_string());
+            }
+        }
+
+        (read_offset + consumed as u64, lines)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::read_new_lines;
+        use std::io::{Seek, SeekFrom, Write};
+
+        fn scratch_file(name: &str) -> std::path::PathBuf {
+            let mut path = std::env::temp_dir();
+            path.push(format!(
+                "operator_log_stream_test_{}_{}.tmp",
+                std::process::id(),
+                name
+            ));
+            path
+        }
+
+        #[test]
+        fn reads_only_complete_lines_and_holds_back_the_partial_tail() {
+            let path = scratch_file("partial_tail");
+            std::fs::write(&path, b"one\ntwo\nthree-in-progress").unwrap();
+            let mut file = std::fs::File::open(&path).unwrap();
+
+            let (offset, lines) = read_new_lines(&mut file, 0);
+
+            assert_eq!(lines, vec!["one".to_string(), "two".to_string()]);
+            assert_eq!(offset, "one\ntwo\n".len() as u64);
+            std::fs::remove_file(&path).unwrap();
+        }
+
+        #[test]
+        fn detects_truncation_and_resets_to_the_start() {
+            let path = scratch_file("truncation");
+            std::fs::write(&path, b"first\nsecond\n").unwrap();
+            let stale_offset = std::fs::metadata(&path).unwrap().len();
+
+            // Simulate rotation: the file underneath the tracked offset is
+            // replaced by a shorter one.
+            let mut file = std::fs::OpenOptions::new()
+                .write(true)
+                .truncate(true)
+                .open(&path)
+                .unwrap();
+            file.write_all(b"fresh\n").unwrap();
+            file.seek(SeekFrom::Start(0)).unwrap();
+
+            let mut reopened = std::fs::File::open(&path).unwrap();
+            let (offset, lines) = read_new_lines(&mut reopened, stale_offset);
+
+            assert_eq!(lines, vec!["fresh".to_string()]);
+            assert_eq!(offset, "fresh\n".len() as u64);
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+}
+
+#[cfg(feature = "log-watch")]
+pub use stream::{start_operator_log_stream, stop_operator_log_stream};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn tail_lines_on_an_empty_file_returns_nothing() {
+        let mut file = tempfile_with_contents(b"");
+        let result = tail_lines(&mut file, 5).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn tail_lines_without_a_trailing_newline_still_returns_the_last_line() {
+        let mut file = tempfile_with_contents(b"one\ntwo\nthree");
+        let result = tail_lines(&mut file, 2).unwrap();
+        assert_eq!(result, vec!["three".to_string(), "two".to_string()]);
+    }
+
+    #[test]
+    fn tail_lines_returns_newest_first() {
+        let mut file = tempfile_with_contents(b"A\nB\nC\nD\n");
+        let result = tail_lines(&mut file, 2).unwrap();
+        assert_eq!(result, vec!["D".to_string(), "C".to_string()]);
+    }
+
+    #[test]
+    fn tail_lines_handles_a_chunk_boundary_that_lands_exactly_on_a_newline() {
+        // Build a prefix exactly one chunk long, ending in '\n' right at the
+        // chunk boundary, then a suffix exactly one more chunk long holding
+        // four lines, the first of which ("z") starts right at that boundary.
+        // Asking for 4 lines, the last chunk has only 4 newlines, so the scan
+        // reads the prefix chunk as well; "z" must still come back whole.
+        let chunk = TAIL_CHUNK_SIZE as usize;
+        let prefix = format!("{}\n", "x".repeat(chunk - 1));
+        assert_eq!(prefix.len(), chunk);
+
+        let filler = "y".repeat(chunk - "z\n".len() - 1 - "boundary-line\nlast\n".len());
+        let suffix = format!("z\n{}\nboundary-line\nlast\n", filler);
+        assert_eq!(suffix.len(), chunk);
+
+        let mut file = tempfile_with_contents(format!("{}{}", prefix, suffix).as_bytes());
+        let result = tail_lines(&mut file, 4).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                "last".to_string(),
+                "boundary-line".to_string(),
+                filler,
+                "z".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn tail_lines_reassembles_a_utf8_character_split_across_a_chunk_boundary() {
+        // Place a 3-byte UTF-8 character ('€') so the backward scan's chunk
+        // boundary falls inside it (one byte into the character). Chunks
+        // are raw bytes concatenated before a single final decode, so this
+        // must reassemble cleanly rather than decoding each chunk on its own
+        // (which would replace the split bytes with U+FFFD on both sides).
+        let chunk = TAIL_CHUNK_SIZE as usize;
+        let lead = "y".to_string();
+        let filler = "y".repeat(chunk - 8);
+        let contents = format!("{}€{}\nlast\n", lead, filler);
+        assert_eq!(contents.len(), chunk + 2);
+
+        let mut file = tempfile_with_contents(contents.as_bytes());
+        let result = tail_lines(&mut file, 2).unwrap();
+
+        assert_eq!(result[0], "last");
+        assert_eq!(result[1], format!("{}€{}", lead, filler));
+    }
+
+    fn tempfile_with_contents(contents: &[u8]) -> File {
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "operator_log_tail_test_{}_{}.tmp",
+            std::process::id(),
+            unix_timestamp_millis()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        let file = File::open(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        file
+    }
+
+    #[test]
+    fn extract_timestamp_field_reads_a_positive_or_negative_integer() {
+        assert_eq!(
+            extract_timestamp_field(r#"{"timestamp":1700000000,"level":"info"}"#),
+            Some(1700000000)
+        );
+        assert_eq!(
+            extract_timestamp_field(r#"{"timestamp":-5,"level":"info"}"#),
+            Some(-5)
+        );
+    }
+
+    #[test]
+    fn extract_timestamp_field_returns_none_when_the_key_is_missing() {
+        assert_eq!(extract_timestamp_field(r#"{"level":"info"}"#), None);
+    }
+
+    #[test]
+    fn describe_parse_error_reports_line_and_column() {
+        let err = serde_json::from_str::<OperatorLogEntry>("{\n  \"level\": \"bogus\"\n}")
+            .unwrap_err();
+        let message = describe_parse_error(&err);
+        assert!(message.starts_with("invalid operator log entry at line 2, column"));
+    }
+
+    #[test]
+    fn segment_overlaps_query_excludes_segments_entirely_before_since_ts() {
+        let record = SegmentIndexRecord {
+            segment: "OPERATOR_LOG.1.jsonl".to_string(),
+            first_ts: 100,
+            last_ts: 200,
+        };
+        let filter = OperatorLogQuery {
+            since_ts: Some(201),
+            ..Default::default()
+        };
+        assert!(!segment_overlaps_query(&record, &filter));
+    }
+
+    #[test]
+    fn segment_overlaps_query_excludes_segments_entirely_after_until_ts() {
+        let record = SegmentIndexRecord {
+            segment: "OPERATOR_LOG.1.jsonl".to_string(),
+            first_ts: 100,
+            last_ts: 200,
+        };
+        let filter = OperatorLogQuery {
+            until_ts: Some(99),
+            ..Default::default()
+        };
+        assert!(!segment_overlaps_query(&record, &filter));
+    }
+
+    #[test]
+    fn segment_overlaps_query_includes_segments_within_range() {
+        let record = SegmentIndexRecord {
+            segment: "OPERATOR_LOG.1.jsonl".to_string(),
+            first_ts: 100,
+            last_ts: 200,
+        };
+        let filter = OperatorLogQuery {
+            since_ts: Some(150),
+            until_ts: Some(250),
+            ..Default::default()
+        };
+        assert!(segment_overlaps_query(&record, &filter));
+    }
+
+    // `LOG_PATH`/`INDEX_PATH` are fixed relative paths, so rotation and query
+    // tests run in their own temp working directory, serialized by this lock
+    // to avoid stepping on each other (cargo test runs tests in parallel by
+    // default within a binary).
+    static CWD_LOCK: Mutex<()> = Mutex::new(());
+
+    struct ScratchDir {
+        original: std::path::PathBuf,
+        path: std::path::PathBuf,
+    }
+
+    impl ScratchDir {
+        fn enter(name: &str) -> Self {
+            let original = std::env::current_dir().unwrap();
+            let mut path = std::env::temp_dir();
+            path.push(format!(
+                "operator_log_test_dir_{}_{}",
+                std::process::id(),
+                name
+            ));
+            std::fs::create_dir_all(&path).unwrap();
+            std::env::set_current_dir(&path).unwrap();
+            ScratchDir { original, path }
+        }
+    }
+
+    impl Drop for ScratchDir {
+        fn drop(&mut self) {
+            let _ = std::env::set_current_dir(&self.original);
+            let _ = std::fs::remove_dir_all(&self.path);
+        }
+    }
+
+    #[test]
+    fn rotation_then_query_finds_entries_in_the_rotated_segment() {
+        let _guard = CWD_LOCK.lock().unwrap();
+        let _dir = ScratchDir::enter("rotation_then_query");
+
+        let old_entry = r#"{"timestamp":1000,"level":"info","actor":"a","action":"old","payload":null}"#;
+        std::fs::write(LOG_PATH, format!("{}\n", old_entry)).unwrap();
+        rotate_operator_log().unwrap();
+
+        let new_entry = r#"{"timestamp":2000,"level":"info","actor":"a","action":"new","payload":null}"#;
+        std::fs::write(LOG_PATH, format!("{}\n", new_entry)).unwrap();
+
+        let results = query_operator_log(OperatorLogQuery {
+            since_ts: Some(500),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].action, "old");
+        assert_eq!(results[1].action, "new");
+    }
+
+    #[test]
+    fn query_with_since_ts_after_the_rotated_segment_only_returns_the_active_log() {
+        let _guard = CWD_LOCK.lock().unwrap();
+        let _dir = ScratchDir::enter("query_since_ts_excludes_old_segment");
+
+        let old_entry = r#"{"timestamp":1000,"level":"info","actor":"a","action":"old","payload":null}"#;
+        std::fs::write(LOG_PATH, format!("{}\n", old_entry)).unwrap();
+        rotate_operator_log().unwrap();
+
+        let new_entry = r#"{"timestamp":2000,"level":"info","actor":"a","action":"new","payload":null}"#;
+        std::fs::write(LOG_PATH, format!("{}\n", new_entry)).unwrap();
+
+        let results = query_operator_log(OperatorLogQuery {
+            since_ts: Some(1500),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].action, "new");
+    }
 }